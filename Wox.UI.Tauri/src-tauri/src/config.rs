@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Parsed view of `~/.wox/config`, a simple `key = value` file (one entry per
+/// line, `#` comments ignored). Missing file yields an empty config so every
+/// feature that reads it can fall back to its own default.
+#[derive(Default)]
+pub struct Config {
+    entries: HashMap<String, String>,
+}
+
+fn config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| {
+        let mut path = PathBuf::from(home);
+        path.push(".wox");
+        path.push("config");
+        path
+    })
+}
+
+impl Config {
+    /// Load `~/.wox/config`, returning an empty config when it is absent.
+    pub fn load() -> Self {
+        let mut entries = HashMap::new();
+        if let Some(path) = config_path() {
+            if let Ok(contents) = fs::read_to_string(path) {
+                for line in contents.lines() {
+                    let line = line.trim();
+                    if line.is_empty() || line.starts_with('#') {
+                        continue;
+                    }
+                    if let Some((key, value)) = line.split_once('=') {
+                        entries.insert(key.trim().to_string(), value.trim().to_string());
+                    }
+                }
+            }
+        }
+        Config { entries }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(|s| s.as_str())
+    }
+
+    /// Read a boolean flag, treating `true`/`1`/`yes` (case-insensitive) as set.
+    pub fn get_bool(&self, key: &str) -> bool {
+        matches!(
+            self.get(key).map(|v| v.to_ascii_lowercase()).as_deref(),
+            Some("true") | Some("1") | Some("yes")
+        )
+    }
+}
+
+/// Whether the panel should be pinned onto every virtual desktop. A
+/// `--visible-on-all-workspaces` CLI flag wins over the `~/.wox/config` entry
+/// of the same name so it can be toggled for a single launch.
+pub fn visible_on_all_workspaces() -> bool {
+    if std::env::args().any(|arg| arg == "--visible-on-all-workspaces") {
+        return true;
+    }
+    Config::load().get_bool("visible_on_all_workspaces")
+}
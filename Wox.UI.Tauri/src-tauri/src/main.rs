@@ -13,7 +13,13 @@ use std::thread::spawn;
 use sysinfo::{Pid, System, SystemExt};
 use tauri::Manager;
 
+mod config;
+mod tray;
+mod updater;
 mod websocket;
+mod window_state;
+
+use window_state::StateFlags;
 
 #[tauri::command]
 fn get_server_port() -> String {
@@ -54,24 +60,31 @@ fn init_log_file() {
     }
 }
 
-fn check_process(pid: i32) -> bool {
-    let mut system = System::new_all();
-    system.refresh_processes();
-    system.process(Pid::from(pid as usize)).is_some()
-}
-
 fn check_wox_alive() {
     let args: Vec<String> = env::args().collect();
     if args.len() > 2 {
         let wox_pid = args[2].parse::<i32>().unwrap();
+        let pid = Pid::from(wox_pid as usize);
+
+        // poll interval is configurable via args[3], defaulting to 3s so
+        // low-power machines can reduce wakeups
+        let interval = if args.len() > 3 {
+            args[3].parse::<u64>().unwrap_or(3)
+        } else {
+            3
+        };
+
+        // Build the System once and only refresh the single parent PID each
+        // tick, instead of scanning every process on the machine.
+        let mut system = System::new();
 
         loop {
-            if !check_process(wox_pid) {
+            if !system.refresh_process(pid) {
                 info!("wox process is not alive, exit ui process");
                 std::process::exit(0);
             } else {
                 info!("wox process is alive");
-                std::thread::sleep(std::time::Duration::from_secs(3));
+                std::thread::sleep(std::time::Duration::from_secs(interval));
             }
         }
     }
@@ -88,42 +101,126 @@ fn main() {
         use tauri_nspanel::cocoa::appkit::{NSMainMenuWindowLevel, NSWindowCollectionBehavior};
         use tauri_nspanel::WindowExt;
         tauri::Builder::default()
+            .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+                // A second launch must focus the existing panel, not spawn a
+                // duplicate UI process.
+                if let Some(window) = app.get_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                    // bring the NSPanel back to the front of the active space
+                    if let Ok(panel) = window.to_panel() {
+                        panel.set_level(NSMainMenuWindowLevel + 1);
+                    }
+                }
+            }))
             .plugin(tauri_nspanel::init())
+            .system_tray(tray::build())
+            .on_system_tray_event(tray::on_event)
             .setup(|app| {
                 let window = app.get_window("main").unwrap();
+                // restore the remembered geometry before the window is shown
+                window_state::apply_state(&window, StateFlags::default());
                 // hide the dock icon
                 app.set_activation_policy(tauri::ActivationPolicy::Accessory);
 
                 let panel = window.to_panel().unwrap();
                 // Set panel above the main menu window level
                 panel.set_level(NSMainMenuWindowLevel + 1);
-                // Ensure that the panel can display over the top of fullscreen apps
-                panel.set_collection_behaviour(NSWindowCollectionBehavior::NSWindowCollectionBehaviorTransient
-                    | NSWindowCollectionBehavior::NSWindowCollectionBehaviorMoveToActiveSpace
-                );
-
+                // Ensure that the panel can display over the top of fullscreen apps.
+                // When `visible_on_all_workspaces` is set the panel is pinned to
+                // every space; otherwise it follows the active space as before.
+                if config::visible_on_all_workspaces() {
+                    panel.set_collection_behaviour(NSWindowCollectionBehavior::NSWindowCollectionBehaviorCanJoinAllSpaces
+                        | NSWindowCollectionBehavior::NSWindowCollectionBehaviorFullScreenAuxiliary
+                    );
+                } else {
+                    panel.set_collection_behaviour(NSWindowCollectionBehavior::NSWindowCollectionBehaviorTransient
+                        | NSWindowCollectionBehavior::NSWindowCollectionBehaviorMoveToActiveSpace
+                    );
+                }
+
+                // Seed the tray label from the window's actual starting
+                // visibility instead of waiting for the first focus change.
+                tray::sync_toggle_label(&window);
+
+                updater::spawn_check(window.clone());
                 spawn(move || {
                     websocket::conn(window);
                 });
 
                 Ok(())
             })
-            .invoke_handler(tauri::generate_handler![get_server_port, log_ui])
+            .on_window_event(persist_on_window_event)
+            .invoke_handler(tauri::generate_handler![
+                get_server_port,
+                log_ui,
+                window_state::save_window_state,
+                window_state::restore_window_state,
+                updater::apply_staged_update
+            ])
             .run(tauri::generate_context!()).expect("error while running tauri application");
     }
 
     #[cfg(not(target_os = "macos"))]
     {
         tauri::Builder::default()
+            .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+                // A second launch must focus the existing window, not spawn a
+                // duplicate UI process.
+                if let Some(window) = app.get_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }))
+            .system_tray(tray::build())
+            .on_system_tray_event(tray::on_event)
             .setup(|app| {
                 let window = app.get_window("main").unwrap();
+                // restore the remembered geometry before the window is shown
+                window_state::apply_state(&window, StateFlags::default());
+                // Pin the launcher onto every virtual desktop when requested,
+                // mirroring the macOS CanJoinAllSpaces behavior.
+                if config::visible_on_all_workspaces() {
+                    let _ = window.set_visible_on_all_workspaces(true);
+                }
+
+                // Seed the tray label from the window's actual starting
+                // visibility instead of waiting for the first focus change.
+                tray::sync_toggle_label(&window);
+
+                updater::spawn_check(window.clone());
                 spawn(move || {
                     websocket::conn(window);
                 });
 
                 Ok(())
             })
-            .invoke_handler(tauri::generate_handler![get_server_port, log_ui])
+            .on_window_event(persist_on_window_event)
+            .invoke_handler(tauri::generate_handler![
+                get_server_port,
+                log_ui,
+                window_state::save_window_state,
+                window_state::restore_window_state,
+                updater::apply_staged_update
+            ])
             .run(tauri::generate_context!()).expect("error while running tauri application");
     }
+}
+
+/// Persist the `main` window's geometry whenever the user moves, resizes, or
+/// closes it, so the next launch restores where they left off.
+fn persist_on_window_event(event: tauri::GlobalWindowEvent) {
+    use tauri::WindowEvent;
+    if event.window().label() != "main" {
+        return;
+    }
+    match event.event() {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) | WindowEvent::CloseRequested { .. } => {
+            window_state::persist_state(event.window(), StateFlags::default());
+        }
+        // keep the tray toggle label in sync when focus changes the panel's
+        // apparent visibility
+        WindowEvent::Focused(_) => tray::sync_toggle_label(event.window()),
+        _ => {}
+    }
 }
\ No newline at end of file
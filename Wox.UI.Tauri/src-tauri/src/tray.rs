@@ -0,0 +1,89 @@
+use std::env;
+
+use sysinfo::{Pid, ProcessExt, Signal, System, SystemExt};
+use tauri::{
+    AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
+    SystemTrayMenuItem, Window,
+};
+
+const TOGGLE_ID: &str = "toggle";
+const QUIT_ID: &str = "quit";
+
+/// Build the tray menu. The toggle item starts hidden-aware: its label is kept
+/// in sync with the window's visibility by [`sync_toggle_label`].
+pub fn build() -> SystemTray {
+    let toggle = CustomMenuItem::new(TOGGLE_ID, "Hide Wox");
+    let quit = CustomMenuItem::new(QUIT_ID, "Quit");
+    let menu = SystemTrayMenu::new()
+        .add_item(toggle)
+        .add_native_item(SystemTrayMenuItem::Separator)
+        .add_item(quit);
+    SystemTray::new().with_menu(menu)
+}
+
+/// Dispatch tray clicks: a left click toggles the panel, the toggle item flips
+/// visibility, and Quit tears down the UI and signals the watched Wox process.
+pub fn on_event(app: &AppHandle, event: SystemTrayEvent) {
+    match event {
+        SystemTrayEvent::LeftClick { .. } => {
+            if let Some(window) = app.get_window("main") {
+                toggle_window(&window);
+            }
+        }
+        SystemTrayEvent::MenuItemClick { id, .. } => match id.as_str() {
+            TOGGLE_ID => {
+                if let Some(window) = app.get_window("main") {
+                    toggle_window(&window);
+                }
+            }
+            QUIT_ID => quit(app),
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+/// Show the window if hidden, hide it if visible, then refresh the tray label.
+pub fn toggle_window(window: &Window) {
+    if window.is_visible().unwrap_or(false) {
+        let _ = window.hide();
+    } else {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    sync_toggle_label(window);
+}
+
+/// Keep the toggle item's label matching the current visibility of `window`.
+pub fn sync_toggle_label(window: &Window) {
+    let visible = window.is_visible().unwrap_or(false);
+    let label = if visible { "Hide Wox" } else { "Show Wox" };
+    let _ = window
+        .app_handle()
+        .tray_handle()
+        .get_item(TOGGLE_ID)
+        .set_title(label);
+}
+
+/// Cleanly terminate the UI process and signal the watched parent Wox PID
+/// (passed as `args[2]`) so the whole launcher shuts down together.
+fn quit(app: &AppHandle) {
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 2 {
+        if let Ok(wox_pid) = args[2].parse::<i32>() {
+            let pid = Pid::from(wox_pid as usize);
+            let mut system = System::new();
+            if system.refresh_process(pid) {
+                if let Some(process) = system.process(pid) {
+                    // Prefer a cooperative SIGTERM so the backend can flush
+                    // and shut down its own listeners/children; only fall
+                    // back to a hard kill if the signal can't be delivered.
+                    if !process.kill_with(Signal::Term).unwrap_or(false) {
+                        process.kill();
+                    }
+                }
+            }
+        }
+    }
+    app.exit(0);
+}
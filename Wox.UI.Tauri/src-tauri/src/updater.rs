@@ -0,0 +1,287 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::thread::spawn;
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tauri::{Manager, Window};
+use tokio::runtime::Builder;
+
+use crate::config::Config;
+use crate::websocket;
+
+/// Event carrying human-readable progress for the update flow.
+const PROGRESS_EVENT: &str = "updater://progress";
+/// Event fired with the staged artifact path once an update is ready to apply.
+const READY_EVENT: &str = "updater://ready";
+
+/// Shape of the release manifest served by the configurable endpoint. `sha256`
+/// is the lowercase hex digest of the artifact at `url`; it is what lets us
+/// detect a tampered download or a compromised/MITM'd endpoint before the
+/// artifact ever touches the running binary.
+#[derive(Deserialize)]
+struct Manifest {
+    version: String,
+    url: String,
+    sha256: String,
+}
+
+fn manifest_url(config: &Config) -> Option<String> {
+    // A CLI arg wins over the config file so a one-off build can point at a
+    // staging endpoint without editing ~/.wox/config. This is the same
+    // positional convention main.rs uses for the port/pid/poll-interval args,
+    // so it lands in the next free slot rather than being sniffed out of
+    // env::args() by shape.
+    let args: Vec<String> = std::env::args().collect();
+    args.get(4)
+        .cloned()
+        .or_else(|| config.get("update_manifest_url").map(|s| s.to_string()))
+}
+
+fn updates_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| {
+        let mut path = PathBuf::from(home);
+        path.push(".wox");
+        path.push("updates");
+        path
+    })
+}
+
+/// Wire the updater into `setup()`. The check is opt-in: it only runs when a
+/// manifest URL is configured and `auto_update` is enabled, and it waits for
+/// the websocket to connect before reaching out to the network.
+pub fn spawn_check(window: Window) {
+    let config = Config::load();
+    if !config.get_bool("auto_update") {
+        info!("updater: auto_update disabled, skipping update check");
+        return;
+    }
+    let url = match manifest_url(&config) {
+        Some(url) => url,
+        None => {
+            info!("updater: no manifest url configured, skipping update check");
+            return;
+        }
+    };
+
+    // Run the check once the backend reports a successful connection. The
+    // state event emits "connecting" before "connected", so we use a
+    // persistent listener that ignores other payloads and fires at most once.
+    // `fired` is what makes this one-shot, and it is checked *inside* the
+    // callback rather than via an `unlisten` call gated on an id stored after
+    // `listen()` returns: that would leave a window where a "connected" event
+    // arriving before the id is stored finds nothing to take, never
+    // unregisters, and the check re-fires on every later reconnect.
+    let fired = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let handle = window.clone();
+    window.listen(websocket::STATE_EVENT, move |event| {
+        if event.payload() != Some("\"connected\"") {
+            return;
+        }
+        if fired.swap(true, std::sync::atomic::Ordering::SeqCst) {
+            return;
+        }
+        let window = handle.clone();
+        let url = url.clone();
+        spawn(move || {
+            let runtime = match Builder::new_current_thread().enable_all().build() {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    error!("updater: failed to build runtime: {}", e);
+                    return;
+                }
+            };
+            runtime.block_on(check_and_stage(window, url));
+        });
+    });
+}
+
+async fn check_and_stage(window: Window, url: String) {
+    info!("updater: checking {}", url);
+    let _ = window.emit(PROGRESS_EVENT, "checking");
+
+    let manifest: Manifest = match fetch_manifest(&url).await {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            error!("updater: failed to fetch manifest: {}", e);
+            return;
+        }
+    };
+
+    let current = env!("CARGO_PKG_VERSION");
+    if !is_newer(&manifest.version, current) {
+        info!(
+            "updater: running {} is up to date (latest {})",
+            current, manifest.version
+        );
+        let _ = window.emit(PROGRESS_EVENT, "up-to-date");
+        return;
+    }
+
+    info!(
+        "updater: newer version {} available (running {})",
+        manifest.version, current
+    );
+    match download(&manifest, &window).await {
+        Ok(path) => {
+            info!("updater: staged update at {}", path.display());
+            // Leave the restart decision to the frontend, which prompts the
+            // user before we swap the running binary.
+            let _ = window.emit(READY_EVENT, path.to_string_lossy().to_string());
+        }
+        Err(e) => error!("updater: download failed: {}", e),
+    }
+}
+
+/// Apply a previously staged update and restart the UI. The frontend prompts
+/// the user, then invokes this with the path it received from [`READY_EVENT`],
+/// so the swap only happens with explicit consent.
+#[tauri::command]
+pub fn apply_staged_update(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let staged = PathBuf::from(&path);
+    if !staged.exists() {
+        return Err(format!("staged update not found: {}", path));
+    }
+
+    // Re-verify against the digest pinned at download time rather than
+    // trusting the file on disk: this is the last checkpoint before we hand
+    // over execution to it, so a staged artifact that was corrupted or
+    // swapped out from under us after download must be caught here.
+    let expected = fs::read_to_string(hash_sidecar(&staged))
+        .map_err(|_| "missing checksum for staged update".to_string())?;
+    let bytes = fs::read(&staged).map_err(|e| e.to_string())?;
+    let actual = sha256_hex(&bytes);
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        let _ = fs::remove_file(&staged);
+        return Err("staged update failed checksum verification".to_string());
+    }
+
+    let current = std::env::current_exe().map_err(|e| e.to_string())?;
+    info!("updater: applying staged update {} -> {}", path, current.display());
+
+    // The running executable's image can't just be overwritten in place:
+    // Windows refuses to open a mapped .exe for writing (sharing violation),
+    // and truncating the file backing a running process's text segment on
+    // Linux is exactly what self-replace tooling exists to avoid. Instead,
+    // rename the running binary out of the way first - both platforms allow
+    // renaming/deleting the file behind an open handle - then move the
+    // staged build into its place.
+    let previous = current.with_extension("old");
+    let _ = fs::remove_file(&previous);
+    fs::rename(&current, &previous).map_err(|e| e.to_string())?;
+    if let Err(e) = fs::rename(&staged, &current).or_else(|_| {
+        fs::copy(&staged, &current)
+            .map(|_| ())
+            .and_then(|_| fs::remove_file(&staged))
+    }) {
+        // best effort: put the running binary back so the app still starts
+        let _ = fs::rename(&previous, &current);
+        return Err(e.to_string());
+    }
+    let _ = fs::remove_file(&previous);
+    let _ = fs::remove_file(hash_sidecar(&staged));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(meta) = fs::metadata(&current) {
+            let mut perms = meta.permissions();
+            perms.set_mode(0o755);
+            let _ = fs::set_permissions(&current, perms);
+        }
+    }
+
+    info!("updater: update applied, restarting");
+    app.restart();
+    Ok(())
+}
+
+async fn fetch_manifest(url: &str) -> Result<Manifest, Box<dyn std::error::Error>> {
+    let body = reqwest::get(url).await?.error_for_status()?.text().await?;
+    Ok(serde_json::from_str(&body)?)
+}
+
+async fn download(
+    manifest: &Manifest,
+    window: &Window,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let _ = window.emit(PROGRESS_EVENT, "downloading");
+    let dir = updates_dir().ok_or("cannot resolve updates directory")?;
+    fs::create_dir_all(&dir)?;
+
+    let file_name = manifest
+        .url
+        .rsplit('/')
+        .next()
+        .filter(|s| !s.is_empty())
+        .unwrap_or("wox-update.bin");
+    let target = dir.join(file_name);
+
+    let bytes = reqwest::get(&manifest.url)
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    // Verify the artifact against the manifest's pinned digest before it ever
+    // touches disk as a staged update: a MITM'd or compromised endpoint can
+    // serve a malicious binary, and this is the only check standing between
+    // that and self-replacing the running executable with it.
+    let actual = sha256_hex(&bytes);
+    if !actual.eq_ignore_ascii_case(&manifest.sha256) {
+        return Err(format!(
+            "checksum mismatch for {}: expected {}, got {}",
+            manifest.url, manifest.sha256, actual
+        )
+        .into());
+    }
+
+    let mut file = File::create(&target)?;
+    file.write_all(&bytes)?;
+    fs::write(hash_sidecar(&target), &actual)?;
+
+    let _ = window.emit(PROGRESS_EVENT, "downloaded");
+    Ok(target)
+}
+
+/// Lowercase hex SHA-256 digest of `bytes`.
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Path of the sidecar file that pins the expected digest for a staged
+/// artifact, so [`apply_staged_update`] can re-verify it independently of the
+/// manifest (which is no longer in scope by the time the frontend calls back).
+fn hash_sidecar(staged: &std::path::Path) -> PathBuf {
+    let mut sidecar = staged.as_os_str().to_owned();
+    sidecar.push(".sha256");
+    PathBuf::from(sidecar)
+}
+
+/// Compare dotted version strings numerically, falling back to a plain string
+/// inequality when a component is non-numeric.
+fn is_newer(candidate: &str, current: &str) -> bool {
+    let a: Vec<&str> = candidate.split('.').collect();
+    let b: Vec<&str> = current.split('.').collect();
+    for i in 0..a.len().max(b.len()) {
+        let x = a.get(i).copied().unwrap_or("0");
+        let y = b.get(i).copied().unwrap_or("0");
+        if x == y {
+            continue;
+        }
+        match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(xn), Ok(yn)) => return xn > yn,
+            // fall back to a plain string comparison when a component is
+            // non-numeric (e.g. a `beta` build suffix)
+            _ => return x > y,
+        }
+    }
+    false
+}
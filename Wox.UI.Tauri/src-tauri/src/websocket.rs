@@ -0,0 +1,106 @@
+use std::env;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use sysinfo::{Pid, System, SystemExt};
+use tauri::{Manager, Window};
+use tokio::runtime::Builder;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+/// Event emitted to the frontend whenever the backend connection changes state.
+pub const STATE_EVENT: &str = "ws://connection-state";
+/// Event carrying a text frame received from the Wox backend.
+const MESSAGE_EVENT: &str = "ws://message";
+
+const BACKOFF_START: Duration = Duration::from_millis(100);
+const BACKOFF_CAP: Duration = Duration::from_secs(5);
+
+/// Entry point kept compatible with `main()`: owns a single-threaded tokio
+/// runtime and drives the reconnect loop until the parent Wox process dies.
+pub fn conn(window: Window) {
+    let runtime = match Builder::new_current_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            error!("failed to build websocket runtime: {}", e);
+            return;
+        }
+    };
+    runtime.block_on(reconnect_loop(window));
+}
+
+fn server_port() -> u16 {
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 1 {
+        args[1].parse::<u16>().unwrap_or(34987)
+    } else {
+        34987
+    }
+}
+
+fn wox_pid() -> Option<Pid> {
+    let args: Vec<String> = env::args().collect();
+    if args.len() > 2 {
+        args[2].parse::<i32>().ok().map(|p| Pid::from(p as usize))
+    } else {
+        None
+    }
+}
+
+/// Retry the connection with exponential backoff (100 ms doubling up to a 5 s
+/// cap, reset on a successful handshake) until the watched parent PID is gone.
+async fn reconnect_loop(window: Window) {
+    let url = format!("ws://127.0.0.1:{}/ws", server_port());
+    let pid = wox_pid();
+    let mut system = System::new();
+    let mut backoff = BACKOFF_START;
+
+    loop {
+        if let Some(pid) = pid {
+            if !system.refresh_process(pid) {
+                info!("wox process is gone, stopping websocket loop");
+                return;
+            }
+        }
+
+        emit_state(&window, "connecting");
+        match connect_async(&url).await {
+            Ok((mut stream, _)) => {
+                info!("websocket connected to {}", url);
+                emit_state(&window, "connected");
+                backoff = BACKOFF_START;
+
+                while let Some(message) = stream.next().await {
+                    match message {
+                        Ok(Message::Text(text)) => {
+                            let _ = window.emit(MESSAGE_EVENT, text);
+                        }
+                        Ok(Message::Close(_)) => break,
+                        Ok(_) => {}
+                        Err(e) => {
+                            error!("websocket read error: {}", e);
+                            break;
+                        }
+                    }
+                }
+
+                info!("websocket disconnected from {}", url);
+                emit_state(&window, "disconnected");
+            }
+            Err(e) => {
+                error!("websocket connect failed: {}", e);
+                emit_state(&window, "disconnected");
+            }
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(BACKOFF_CAP);
+    }
+}
+
+fn emit_state(window: &Window, state: &str) {
+    let _ = window.emit(STATE_EVENT, state);
+    // `emit` only reaches JS listeners; also trigger Rust-side listeners such
+    // as the updater's, with the same JSON-encoded payload.
+    window.trigger(STATE_EVENT, serde_json::to_string(state).ok());
+}
@@ -0,0 +1,181 @@
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+use bitflags::bitflags;
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, PhysicalPosition, PhysicalSize, Window};
+
+bitflags! {
+    /// Which window attributes the frontend wants remembered across restarts.
+    #[derive(Serialize, Deserialize)]
+    pub struct StateFlags: u32 {
+        const POSITION   = 1 << 0;
+        const SIZE       = 1 << 1;
+        const VISIBILITY = 1 << 2;
+    }
+}
+
+impl Default for StateFlags {
+    fn default() -> Self {
+        StateFlags::POSITION | StateFlags::SIZE
+    }
+}
+
+/// A snapshot of the `main` window persisted to `~/.wox/window-state.bin`.
+#[derive(Serialize, Deserialize)]
+struct WindowState {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    #[cfg(not(target_os = "macos"))]
+    maximized: bool,
+    // Appended after `maximized` rather than inserted above it so the field
+    // order here matches the order `persist_state` has always written, on the
+    // off chance an older on-disk file is ever made forwards-compatible
+    // later (bincode is positional, not self-describing, so today any file
+    // written before this field existed is simply too short and fails to
+    // deserialize - `persist_state` then falls back to `default()` below).
+    visible: bool,
+}
+
+impl Default for WindowState {
+    // `#[derive(Default)]` would make `visible` default to `false`, which
+    // contradicts `default_visible()` / `apply_state`'s "show unless told
+    // otherwise" behavior and hides the panel the first time state is
+    // persisted without VISIBILITY in `flags` (the `on_window_event` path
+    // never sets it).
+    fn default() -> Self {
+        WindowState {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            #[cfg(not(target_os = "macos"))]
+            maximized: false,
+            visible: default_visible(),
+        }
+    }
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+fn state_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| {
+        let mut path = PathBuf::from(home);
+        path.push(".wox");
+        path.push("window-state.bin");
+        path
+    })
+}
+
+fn read_state() -> Option<WindowState> {
+    let path = state_path()?;
+    let mut file = File::open(path).ok()?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).ok()?;
+    bincode::deserialize(&buf).ok()
+}
+
+/// Capture the current geometry of `window` and write it to disk. Only the
+/// attributes selected in `flags` are captured; the rest keep their previous
+/// value so a partial save does not clobber position when only size changed.
+pub fn persist_state(window: &Window, flags: StateFlags) {
+    let path = match state_path() {
+        Some(path) => path,
+        None => return,
+    };
+
+    let mut state = read_state().unwrap_or_default();
+
+    if flags.contains(StateFlags::POSITION) {
+        if let Ok(position) = window.outer_position() {
+            state.x = position.x;
+            state.y = position.y;
+        }
+    }
+    if flags.contains(StateFlags::SIZE) {
+        if let Ok(size) = window.outer_size() {
+            state.width = size.width;
+            state.height = size.height;
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            state.maximized = window.is_maximized().unwrap_or(false);
+        }
+    }
+    if flags.contains(StateFlags::VISIBILITY) {
+        state.visible = window.is_visible().unwrap_or(true);
+    }
+
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(bytes) = bincode::serialize(&state) {
+        if let Ok(mut file) = File::create(path) {
+            let _ = file.write_all(&bytes);
+        }
+    }
+}
+
+/// Restore the geometry recorded for `window`. Restoration is gated so a panel
+/// that was last placed on a now-disconnected monitor keeps its current
+/// position instead of being dragged off-screen.
+pub fn apply_state(window: &Window, flags: StateFlags) {
+    let state = match read_state() {
+        Some(state) => state,
+        None => return,
+    };
+
+    if flags.contains(StateFlags::SIZE) && state.width > 0 && state.height > 0 {
+        let _ = window.set_size(PhysicalSize::new(state.width, state.height));
+    }
+    if flags.contains(StateFlags::POSITION) && is_on_visible_monitor(window, &state) {
+        let _ = window.set_position(PhysicalPosition::new(state.x, state.y));
+    }
+    #[cfg(not(target_os = "macos"))]
+    if flags.contains(StateFlags::SIZE) && state.maximized {
+        let _ = window.maximize();
+    }
+    if flags.contains(StateFlags::VISIBILITY) {
+        if state.visible {
+            let _ = window.show();
+        } else {
+            let _ = window.hide();
+        }
+    }
+}
+
+/// True when the saved top-left corner still falls inside one of the currently
+/// connected monitors, so we never restore onto a disconnected display.
+fn is_on_visible_monitor(window: &Window, state: &WindowState) -> bool {
+    let monitors = match window.available_monitors() {
+        Ok(monitors) => monitors,
+        Err(_) => return false,
+    };
+    monitors.iter().any(|monitor| {
+        let pos = monitor.position();
+        let size = monitor.size();
+        state.x >= pos.x
+            && state.y >= pos.y
+            && state.x < pos.x + size.width as i32
+            && state.y < pos.y + size.height as i32
+    })
+}
+
+#[tauri::command]
+pub fn save_window_state(app: tauri::AppHandle, flags: u32) {
+    if let Some(window) = app.get_window("main") {
+        persist_state(&window, StateFlags::from_bits_truncate(flags));
+    }
+}
+
+#[tauri::command]
+pub fn restore_window_state(app: tauri::AppHandle, flags: u32) {
+    if let Some(window) = app.get_window("main") {
+        apply_state(&window, StateFlags::from_bits_truncate(flags));
+    }
+}